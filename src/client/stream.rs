@@ -212,10 +212,8 @@ impl JsonStreamParser {
                     }
                     self.balances.push(ch);
                 }
-                '[' => {
-                    if self.start.is_some() {
-                        self.balances.push(ch);
-                    }
+                '[' if self.start.is_some() => {
+                    self.balances.push(ch);
                 }
                 '}' => {
                     self.balances.pop();
@@ -250,9 +248,9 @@ mod tests {
         let len = text.len();
         let cut1 = rng.random_range(1..len - 1);
         let cut2 = rng.random_range(cut1 + 1..len);
-        let chunk1 = text[..cut1].as_bytes().to_vec();
-        let chunk2 = text[cut1..cut2].as_bytes().to_vec();
-        let chunk3 = text[cut2..].as_bytes().to_vec();
+        let chunk1 = text[..cut1].to_string().into_bytes();
+        let chunk2 = text[cut1..cut2].to_string().into_bytes();
+        let chunk3 = text[cut2..].to_string().into_bytes();
         vec![chunk1, chunk2, chunk3]
     }
 
@@ -53,6 +53,7 @@ const ROLES_DIR_NAME: &str = "roles";
 const MACROS_DIR_NAME: &str = "macros";
 const ENV_FILE_NAME: &str = ".env";
 const MESSAGES_FILE_NAME: &str = "messages.md";
+const HISTORY_FILE_NAME: &str = "history.txt";
 const SESSIONS_DIR_NAME: &str = "sessions";
 const RAGS_DIR_NAME: &str = "rags";
 const FUNCTIONS_DIR_NAME: &str = "functions";
@@ -108,6 +109,8 @@ pub struct Config {
     pub stream: bool,
     pub save: bool,
     pub keybindings: String,
+    pub repl_history_size: usize,
+    pub repl_key_commands: IndexMap<String, String>,
     pub editor: Option<String>,
     pub wrap: Option<String>,
     pub wrap_code: bool,
@@ -184,6 +187,8 @@ impl Default for Config {
             stream: true,
             save: false,
             keybindings: "emacs".into(),
+            repl_history_size: 1000,
+            repl_key_commands: Default::default(),
             editor: None,
             wrap: None,
             wrap_code: false,
@@ -346,6 +351,13 @@ impl Config {
         }
     }
 
+    pub fn history_file() -> PathBuf {
+        match env::var(get_env_name("history_file")) {
+            Ok(value) => PathBuf::from(value),
+            Err(_) => Self::local_path(HISTORY_FILE_NAME),
+        }
+    }
+
     pub fn sessions_dir(&self) -> PathBuf {
         match &self.agent {
             None => match env::var(get_env_name("sessions_dir")) {
@@ -608,6 +620,7 @@ impl Config {
             ("stream", self.stream.to_string()),
             ("save", self.save.to_string()),
             ("keybindings", self.keybindings.clone()),
+            ("repl_history_size", self.repl_history_size.to_string()),
             ("wrap", wrap),
             ("wrap_code", self.wrap_code.to_string()),
             ("highlight", self.highlight.to_string()),
@@ -620,6 +633,7 @@ impl Config {
             ("macros_dir", display_path(&Self::macros_dir())),
             ("functions_dir", display_path(&Self::functions_dir())),
             ("messages_file", display_path(&self.messages_file())),
+            ("history_file", display_path(&Self::history_file())),
         ];
         if let Ok((_, Some(log_path))) = Self::log_config(self.working_mode.is_serve()) {
             items.push(("log_path", display_path(&log_path)));
@@ -2258,6 +2272,14 @@ impl Config {
                 self.keybindings = v;
             }
         }
+        if let Some(Some(v)) = read_env_value::<usize>(&get_env_name("repl_history_size")) {
+            self.repl_history_size = v;
+        }
+        if let Ok(v) = env::var(get_env_name("repl_key_commands")) {
+            if let Ok(v) = serde_json::from_str(&v) {
+                self.repl_key_commands = v;
+            }
+        }
         if let Some(v) = read_env_value::<String>(&get_env_name("editor")) {
             self.editor = v;
         }
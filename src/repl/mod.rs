@@ -13,23 +13,25 @@ use crate::config::{
 };
 use crate::render::render_error;
 use crate::utils::{
-    abortable_run_with_spinner, create_abort_signal, dimmed_text, set_text, temp_file, AbortSignal,
+    abortable_run_with_spinner, create_abort_signal, dimmed_text, set_text, temp_file,
+    warning_text, AbortSignal,
 };
 
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use fancy_regex::Regex;
 use reedline::{
     default_emacs_keybindings, default_vi_insert_keybindings, default_vi_normal_keybindings,
-    ColumnarMenu, EditCommand, EditMode, Emacs, KeyCode, KeyModifiers, Keybindings, Reedline,
-    ReedlineEvent, ReedlineMenu, ValidationResult, Validator, Vi,
+    ColumnarMenu, EditCommand, EditMode, Emacs, FileBackedHistory, KeyCode, KeyModifiers,
+    Keybindings, Reedline, ReedlineError, ReedlineErrorVariants, ReedlineEvent, ReedlineMenu,
+    ValidationResult, Validator, Vi,
 };
 use reedline::{MenuBuilder, Signal};
 use std::sync::LazyLock;
-use std::{env, process};
+use std::{env, io, process};
 
 const MENU_NAME: &str = "completion_menu";
 
-static REPL_COMMANDS: LazyLock<[ReplCommand; 36]> = LazyLock::new(|| {
+static REPL_COMMANDS: LazyLock<[ReplCommand; 37]> = LazyLock::new(|| {
     [
         ReplCommand::new(".help", "Show this help guide", AssertState::pass()),
         ReplCommand::new(".info", "Show system info", AssertState::pass()),
@@ -175,6 +177,7 @@ static REPL_COMMANDS: LazyLock<[ReplCommand; 36]> = LazyLock::new(|| {
             AssertState::pass(),
         ),
         ReplCommand::new(".copy", "Copy last response", AssertState::pass()),
+        ReplCommand::new(".clear history", "Clear input history", AssertState::pass()),
         ReplCommand::new(".set", "Modify runtime settings", AssertState::pass()),
         ReplCommand::new(
             ".delete",
@@ -231,7 +234,20 @@ Type ".help" for additional help.
             match sig {
                 Ok(Signal::Success(line)) => {
                     self.abort_signal.reset();
-                    match run_repl_command(&self.config, self.abort_signal.clone(), &line).await {
+                    // Write each line right away so it survives a killed terminal, this also
+                    // keeps appends ordered when several REPLs share the history file
+                    if let Err(err) = self.editor.sync_history() {
+                        println!(
+                            "{}",
+                            warning_text(&format!("⚠️ Failed to save history: {err}"))
+                        );
+                    }
+                    let ret = if is_clear_history_command(&line) {
+                        self.clear_history().map(|_| false)
+                    } else {
+                        run_repl_command(&self.config, self.abort_signal.clone(), &line).await
+                    };
+                    match ret {
                         Ok(exit) => {
                             if exit {
                                 break;
@@ -258,11 +274,23 @@ Type ".help" for additional help.
         Ok(())
     }
 
+    fn clear_history(&mut self) -> Result<()> {
+        match self.editor.history_mut().clear() {
+            Ok(()) => {}
+            // The file is only recreated on the next sync, so clearing twice finds nothing to remove
+            Err(ReedlineError(ReedlineErrorVariants::IOError(err)))
+                if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err).context("Failed to clear history"),
+        }
+        println!("✓ Successfully cleared the input history.\n");
+        Ok(())
+    }
+
     fn create_editor(config: &GlobalConfig) -> Result<Reedline> {
         let completer = ReplCompleter::new(config);
         let highlighter = ReplHighlighter::new(config);
         let menu = Self::create_menu();
-        let edit_mode = Self::create_edit_mode(config);
+        let edit_mode = Self::create_edit_mode(config)?;
         let mut editor = Reedline::create()
             .with_completer(Box::new(completer))
             .with_highlighter(Box::new(highlighter))
@@ -274,6 +302,21 @@ Type ".help" for additional help.
             .with_validator(Box::new(ReplValidator))
             .with_ansi_colors(true);
 
+        let history_size = config.read().repl_history_size;
+        if history_size > 0 {
+            let history_path = Config::history_file();
+            match FileBackedHistory::with_file(history_size, history_path.clone()) {
+                Ok(history) => editor = editor.with_history(Box::new(history)),
+                Err(err) => println!(
+                    "{}",
+                    warning_text(&format!(
+                        "⚠️ Failed to load history from '{}', history will not be saved: {err}",
+                        history_path.display()
+                    ))
+                ),
+            }
+        }
+
         if let Ok(cmd) = config.read().editor() {
             let temp_file = temp_file("-repl-", ".md");
             let command = process::Command::new(cmd);
@@ -283,7 +326,10 @@ Type ".help" for additional help.
         Ok(editor)
     }
 
-    fn extra_keybindings(keybindings: &mut Keybindings) {
+    fn extra_keybindings(
+        keybindings: &mut Keybindings,
+        key_commands: &[(KeyModifiers, KeyCode, String)],
+    ) {
         keybindings.add_binding(
             KeyModifiers::NONE,
             KeyCode::Tab,
@@ -302,21 +348,44 @@ Type ".help" for additional help.
             KeyCode::Enter,
             ReedlineEvent::Edit(vec![EditCommand::InsertNewline]),
         );
+        for (modifiers, code, command) in key_commands {
+            keybindings.add_binding(
+                *modifiers,
+                *code,
+                ReedlineEvent::Multiple(vec![
+                    ReedlineEvent::Edit(vec![
+                        EditCommand::Clear,
+                        EditCommand::InsertString(command.clone()),
+                    ]),
+                    ReedlineEvent::Submit,
+                ]),
+            );
+        }
     }
 
-    fn create_edit_mode(config: &GlobalConfig) -> Box<dyn EditMode> {
+    fn create_edit_mode(config: &GlobalConfig) -> Result<Box<dyn EditMode>> {
+        let key_commands = config
+            .read()
+            .repl_key_commands
+            .iter()
+            .map(|(key, command)| {
+                let (modifiers, code) = parse_key_chord(key)
+                    .with_context(|| format!("Invalid repl_key_commands entry '{key}'"))?;
+                Ok((modifiers, code, command.clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
         let edit_mode: Box<dyn EditMode> = if config.read().keybindings == "vi" {
             let mut normal_keybindings = default_vi_normal_keybindings();
             let mut insert_keybindings = default_vi_insert_keybindings();
-            Self::extra_keybindings(&mut normal_keybindings);
-            Self::extra_keybindings(&mut insert_keybindings);
+            Self::extra_keybindings(&mut normal_keybindings, &key_commands);
+            Self::extra_keybindings(&mut insert_keybindings, &key_commands);
             Box::new(Vi::new(insert_keybindings, normal_keybindings))
         } else {
             let mut keybindings = default_emacs_keybindings();
-            Self::extra_keybindings(&mut keybindings);
+            Self::extra_keybindings(&mut keybindings, &key_commands);
             Box::new(Emacs::new(keybindings))
         };
-        edit_mode
+        Ok(edit_mode)
     }
 
     fn create_menu() -> ReedlineMenu {
@@ -688,6 +757,9 @@ pub async fn run_repl_command(
                 Some("messages") => {
                     bail!("Use '.empty session' instead");
                 }
+                Some("history") => {
+                    bail!("Input history can only be cleared in the REPL");
+                }
                 _ => unknown_command()?,
             },
             _ => unknown_command()?,
@@ -778,6 +850,91 @@ fn parse_command(line: &str) -> Option<(&str, Option<&str>)> {
     }
 }
 
+fn is_clear_history_command(line: &str) -> bool {
+    matches!(parse_command(line), Some((".clear", Some("history"))))
+}
+
+/// Parse a key chord such as `f5`, `ctrl-g` or `alt-shift-r`
+fn parse_key_chord(key: &str) -> Result<(KeyModifiers, KeyCode)> {
+    let invalid_key = || anyhow!("Invalid key '{key}'");
+    let key = key.trim().to_lowercase();
+    let separator = key
+        .char_indices()
+        .rev()
+        .skip(1)
+        .find(|(_, ch)| matches!(ch, '-' | '+'));
+    let (modifier_names, code_name) = match separator {
+        Some((i, _)) => (&key[..i], &key[i + 1..]),
+        None => ("", key.as_str()),
+    };
+    let mut modifiers = KeyModifiers::NONE;
+    for name in modifier_names.split(['-', '+']).filter(|v| !v.is_empty()) {
+        modifiers |= match name {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(invalid_key()),
+        };
+    }
+    let code = match code_name {
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "esc" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        _ => {
+            let mut chars = code_name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) => KeyCode::Char(ch),
+                (Some('f'), Some(_)) => {
+                    KeyCode::F(code_name[1..].parse().map_err(|_| invalid_key())?)
+                }
+                _ => return Err(invalid_key()),
+            }
+        }
+    };
+    // Plain or shifted characters and editing keys are bound before the editor handles them,
+    // so binding them would block typing, submitting or history recall
+    if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+        match code {
+            KeyCode::Char(_) => {
+                bail!(
+                    "{}, printable keys require a ctrl or alt modifier",
+                    invalid_key()
+                )
+            }
+            KeyCode::Enter
+            | KeyCode::Tab
+            | KeyCode::Esc
+            | KeyCode::Backspace
+            | KeyCode::Delete
+            | KeyCode::Home
+            | KeyCode::End
+            | KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::Left
+            | KeyCode::Right => {
+                bail!(
+                    "{}, editing keys require a ctrl or alt modifier",
+                    invalid_key()
+                )
+            }
+            _ => {}
+        }
+    }
+    Ok((modifiers, code))
+}
+
 fn split_first_arg(args: Option<&str>) -> Option<(&str, Option<&str>)> {
     args.map(|v| match v.split_once(' ') {
         Some((subcmd, args)) => (subcmd, Some(args.trim())),
@@ -880,6 +1037,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_clear_history_command() {
+        assert!(is_clear_history_command(".clear history"));
+        assert!(is_clear_history_command("  .clear   history "));
+        assert!(!is_clear_history_command(".clear messages"));
+        assert!(!is_clear_history_command(".clear"));
+        assert!(!is_clear_history_command("clear history"));
+    }
+
+    #[test]
+    fn test_parse_key_chord() {
+        assert_eq!(
+            parse_key_chord("f5").ok(),
+            Some((KeyModifiers::NONE, KeyCode::F(5)))
+        );
+        assert_eq!(
+            parse_key_chord("Ctrl-G").ok(),
+            Some((KeyModifiers::CONTROL, KeyCode::Char('g')))
+        );
+        assert_eq!(
+            parse_key_chord("alt+shift+enter").ok(),
+            Some((KeyModifiers::ALT | KeyModifiers::SHIFT, KeyCode::Enter))
+        );
+        assert_eq!(
+            parse_key_chord("ctrl--").ok(),
+            Some((KeyModifiers::CONTROL, KeyCode::Char('-')))
+        );
+        assert!(parse_key_chord("q").is_err());
+        assert!(parse_key_chord("space").is_err());
+        assert!(parse_key_chord("shift-a").is_err());
+        assert!(parse_key_chord("enter").is_err());
+        assert!(parse_key_chord("up").is_err());
+        assert!(parse_key_chord("shift-backspace").is_err());
+        assert_eq!(
+            parse_key_chord("alt-up").ok(),
+            Some((KeyModifiers::ALT, KeyCode::Up))
+        );
+        assert_eq!(
+            parse_key_chord("pageup").ok(),
+            Some((KeyModifiers::NONE, KeyCode::PageUp))
+        );
+        assert!(parse_key_chord("super-a").is_err());
+        assert!(parse_key_chord("fx").is_err());
+    }
+
     #[test]
     fn test_split_args_text() {
         assert_eq!(split_args_text("", false), (vec![], ""));